@@ -1,13 +1,24 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, VecDeque};
 use std::ffi::OsStr;
+use std::hash::{Hash, Hasher};
+use std::io::Cursor;
+use std::sync::Mutex;
 
-use typst::image::{Image, ImageFormat, RasterFormat, VectorFormat};
+use image::imageops::FilterType;
+use once_cell::sync::Lazy;
+use typst::image::{Bytes, Image, ImageFormat, RasterFormat, VectorFormat};
 
 use crate::prelude::*;
 
 /// # Image
 /// A raster or vector graphic.
 ///
-/// Supported formats are PNG, JPEG, GIF and SVG.
+/// Supported formats are PNG, JPEG, GIF, SVG, WebP, BMP and TIFF.
+///
+/// Instead of a path, raw image bytes can be passed in directly, in which
+/// case the `format` argument should be set to disambiguate the format
+/// that the bytes are encoded in.
 ///
 /// ## Example
 /// ```example
@@ -19,8 +30,12 @@ use crate::prelude::*;
 /// ```
 ///
 /// ## Parameters
-/// - path: `EcoString` (positional, required)
-///   Path to an image file.
+/// - source: `Readable` (positional, required)
+///   Path to an image file or raw bytes making up an image.
+///
+/// - format: `EcoString` (named)
+///   The image's format. Detected from the file extension or the bytes'
+///   content if not specified.
 ///
 /// - width: `Rel<Length>` (named)
 ///   The width of the image.
@@ -28,6 +43,10 @@ use crate::prelude::*;
 /// - height: `Rel<Length>` (named)
 ///   The height of the image.
 ///
+/// - alignment: `Align` (named)
+///   How to align the image within its area once fitted. Defaults to
+///   centering the image both horizontally and vertically.
+///
 /// ## Category
 /// visualize
 #[func]
@@ -44,19 +63,36 @@ impl ImageNode {
     /// How the image should adjust itself to a given area.
     pub const FIT: ImageFit = ImageFit::Cover;
 
+    /// How to align the image within its area once fitted.
+    pub const ALIGNMENT: Smart<Align> = Smart::Auto;
+
+    /// The resolution at which oversized raster images are downscaled
+    /// before being embedded, in pixels per inch.
+    pub const DPI: f64 = 144.0;
+
     fn construct(vm: &Vm, args: &mut Args) -> SourceResult<Content> {
-        let Spanned { v: path, span } =
-            args.expect::<Spanned<EcoString>>("path to image file")?;
-
-        let full = vm.locate(&path).at(span)?;
-        let buffer = vm.world().file(&full).at(span)?;
-        let ext = full.extension().and_then(OsStr::to_str).unwrap_or_default();
-        let format = match ext.to_lowercase().as_str() {
-            "png" => ImageFormat::Raster(RasterFormat::Png),
-            "jpg" | "jpeg" => ImageFormat::Raster(RasterFormat::Jpg),
-            "gif" => ImageFormat::Raster(RasterFormat::Gif),
-            "svg" | "svgz" => ImageFormat::Vector(VectorFormat::Svg),
-            _ => bail!(span, "unknown image format"),
+        let Spanned { v: source, span } =
+            args.expect::<Spanned<Readable>>("path to image file or raw data")?;
+        let format_arg = args.named::<Spanned<EcoString>>("format")?;
+
+        let (buffer, format) = match source {
+            Readable::Path(path) => {
+                let full = vm.locate(&path).at(span)?;
+                let buffer = vm.world().file(&full).at(span)?;
+                let ext = full.extension().and_then(OsStr::to_str).unwrap_or_default();
+                let format = match format_arg {
+                    Some(Spanned { v, span }) => parse_format(&v).at(span)?,
+                    None => determine_format(ext, &buffer).at(span)?,
+                };
+                (buffer, format)
+            }
+            Readable::Bytes(bytes) => {
+                let format = match format_arg {
+                    Some(Spanned { v, span }) => parse_format(&v).at(span)?,
+                    None => sniff_format(&bytes).at(span)?,
+                };
+                (bytes, format)
+            }
         };
 
         let image = Image::new(buffer, format).at(span)?;
@@ -66,6 +102,247 @@ impl ImageNode {
     }
 }
 
+/// Either a path to a file or raw bytes making up a file's content.
+enum Readable {
+    /// A path to load a file from.
+    Path(EcoString),
+    /// Raw bytes making up the file's content.
+    Bytes(Bytes),
+}
+
+castable! {
+    Readable,
+    Expected: "string or bytes",
+    v: EcoString => Self::Path(v),
+    v: Bytes => Self::Bytes(v),
+}
+
+/// Parse an explicit `format` argument, erroring on names that aren't
+/// recognized rather than silently falling back to sniffing the content —
+/// an invalid `format` is a mistake in the call the user should fix, not
+/// something to paper over.
+fn parse_format(name: &str) -> StrResult<ImageFormat> {
+    let format = match name.to_lowercase().as_str() {
+        "png" => ImageFormat::Raster(RasterFormat::Png),
+        "jpg" | "jpeg" => ImageFormat::Raster(RasterFormat::Jpg),
+        "gif" => ImageFormat::Raster(RasterFormat::Gif),
+        "webp" => ImageFormat::Raster(RasterFormat::Webp),
+        "bmp" => ImageFormat::Raster(RasterFormat::Bmp),
+        "tif" | "tiff" => ImageFormat::Raster(RasterFormat::Tiff),
+        "svg" | "svgz" => ImageFormat::Vector(VectorFormat::Svg),
+        _ => return Err(format!("unknown image format: {name}").into()),
+    };
+    Ok(format)
+}
+
+/// Determine the image format from a file extension, falling back to
+/// sniffing the file's content if the extension is missing or unrecognized.
+fn determine_format(ext: &str, buffer: &[u8]) -> StrResult<ImageFormat> {
+    let format = match ext.to_lowercase().as_str() {
+        "png" => ImageFormat::Raster(RasterFormat::Png),
+        "jpg" | "jpeg" => ImageFormat::Raster(RasterFormat::Jpg),
+        "gif" => ImageFormat::Raster(RasterFormat::Gif),
+        "webp" => ImageFormat::Raster(RasterFormat::Webp),
+        "bmp" => ImageFormat::Raster(RasterFormat::Bmp),
+        "tif" | "tiff" => ImageFormat::Raster(RasterFormat::Tiff),
+        "svg" | "svgz" => ImageFormat::Vector(VectorFormat::Svg),
+        _ => return sniff_format(buffer),
+    };
+    Ok(format)
+}
+
+/// Guess the image format from its magic bytes.
+fn sniff_format(buffer: &[u8]) -> StrResult<ImageFormat> {
+    let format = if buffer.starts_with(b"\x89PNG\r\n\x1a\n") {
+        ImageFormat::Raster(RasterFormat::Png)
+    } else if buffer.starts_with(b"\xff\xd8\xff") {
+        ImageFormat::Raster(RasterFormat::Jpg)
+    } else if buffer.starts_with(b"GIF87a") || buffer.starts_with(b"GIF89a") {
+        ImageFormat::Raster(RasterFormat::Gif)
+    } else if buffer.len() >= 12
+        && &buffer[0..4] == b"RIFF"
+        && &buffer[8..12] == b"WEBP"
+    {
+        ImageFormat::Raster(RasterFormat::Webp)
+    } else if buffer.starts_with(b"BM") {
+        ImageFormat::Raster(RasterFormat::Bmp)
+    } else if buffer.starts_with(b"II*\0") || buffer.starts_with(b"MM\0*") {
+        ImageFormat::Raster(RasterFormat::Tiff)
+    } else if buffer.trim_ascii_start().starts_with(b"<?xml")
+        || buffer.trim_ascii_start().starts_with(b"<svg")
+    {
+        ImageFormat::Vector(VectorFormat::Svg)
+    } else {
+        return Err("unknown image format".into());
+    };
+    Ok(format)
+}
+
+/// Compute the size of the fitted image within `target`, given whether the
+/// image is wider than the target region (`wide`), its pixel aspect ratio,
+/// and its pixel dimensions. Split out of [`Layout::layout`] so the fit math
+/// can be unit tested without a full layout context.
+fn fit_size(
+    fit: ImageFit,
+    target: Size,
+    wide: bool,
+    px_ratio: f64,
+    pxw: f64,
+    pxh: f64,
+) -> Size {
+    match fit {
+        ImageFit::Cover | ImageFit::Contain => {
+            if wide == (fit == ImageFit::Contain) {
+                Size::new(target.x, target.x / px_ratio)
+            } else {
+                Size::new(target.y * px_ratio, target.y)
+            }
+        }
+        ImageFit::ScaleDown => {
+            let contain = fit_size(ImageFit::Contain, target, wide, px_ratio, pxw, pxh);
+            Size::new(contain.x.min(Abs::pt(pxw)), contain.y.min(Abs::pt(pxh)))
+        }
+        ImageFit::None => Size::new(Abs::pt(pxw), Abs::pt(pxh)),
+        ImageFit::Stretch => target,
+    }
+}
+
+/// Resolve the alignment an image should be placed with, falling back to
+/// centering both horizontally and vertically when none was specified.
+/// Split out of [`Layout::layout`] so the default-resolution logic can be
+/// unit tested without a full layout context.
+fn resolve_alignment(alignment: Smart<Align>) -> Align {
+    alignment.unwrap_or(Align::CENTER_HORIZON)
+}
+
+/// Hash a byte buffer into a 64-bit digest for use as a cache key.
+///
+/// The real `typst::util::hash128` helper isn't part of this trimmed
+/// checkout, so we hash locally with `DefaultHasher` instead of depending on
+/// it.
+fn hash_bytes(bytes: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    bytes.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// An LRU cache from `(source hash, target width, target height)` to the
+/// downscaled image produced for that key, bounded so that repeatedly
+/// downscaling many distinct images cannot grow memory without limit.
+struct DownscaleCache {
+    map: HashMap<(u64, u32, u32), Image>,
+    order: VecDeque<(u64, u32, u32)>,
+}
+
+impl DownscaleCache {
+    fn new() -> Self {
+        Self { map: HashMap::new(), order: VecDeque::new() }
+    }
+
+    fn get(&mut self, key: &(u64, u32, u32)) -> Option<Image> {
+        let image = self.map.get(key)?.clone();
+        self.touch(key);
+        Some(image)
+    }
+
+    fn insert(&mut self, key: (u64, u32, u32), image: Image) {
+        if self.map.insert(key, image).is_none() {
+            self.order.push_back(key);
+            if self.order.len() > DOWNSCALE_CACHE_CAP {
+                if let Some(oldest) = self.order.pop_front() {
+                    self.map.remove(&oldest);
+                }
+            }
+        } else {
+            self.touch(&key);
+        }
+    }
+
+    fn touch(&mut self, key: &(u64, u32, u32)) {
+        if let Some(pos) = self.order.iter().position(|k| k == key) {
+            let key = self.order.remove(pos).unwrap();
+            self.order.push_back(key);
+        }
+    }
+}
+
+/// The maximum number of downscaled images kept in [`DOWNSCALE_CACHE`].
+const DOWNSCALE_CACHE_CAP: usize = 64;
+
+static DOWNSCALE_CACHE: Lazy<Mutex<DownscaleCache>> =
+    Lazy::new(|| Mutex::new(DownscaleCache::new()));
+
+/// Whether an image with the given pixel dimensions actually needs to be
+/// downscaled to fit a target of `pixel_width` × `pixel_height`. Both axes
+/// must already fit for downscaling to be skipped — an image that is
+/// oversized along only one axis still needs resizing.
+fn needs_downscale(
+    pixel_width: u32,
+    pixel_height: u32,
+    image_width: u32,
+    image_height: u32,
+) -> bool {
+    !(pixel_width >= image_width && pixel_height >= image_height)
+}
+
+/// Return a version of `image` downscaled to roughly `pixel_width` ×
+/// `pixel_height` pixels, if it is a raster image with more pixels than
+/// that and downscaling it succeeds. Results are cached by source content
+/// and target size so the same image isn't re-encoded on every layout pass.
+fn downscaled(image: &Image, pixel_width: u32, pixel_height: u32) -> Image {
+    if !matches!(image.format(), ImageFormat::Raster(_))
+        || !needs_downscale(pixel_width, pixel_height, image.width(), image.height())
+    {
+        return image.clone();
+    }
+
+    let key = (hash_bytes(image.data()), pixel_width, pixel_height);
+
+    if let Some(cached) = DOWNSCALE_CACHE.lock().unwrap().get(&key) {
+        return cached;
+    }
+
+    // Decode, resize and re-encode without holding the cache lock: this work
+    // is the expensive part and shouldn't block other images from being
+    // looked up or inserted concurrently.
+    let downscaled = try_downscale(image, pixel_width, pixel_height).unwrap_or_else(|| image.clone());
+    DOWNSCALE_CACHE.lock().unwrap().insert(key, downscaled.clone());
+    downscaled
+}
+
+/// Decode, downscale and re-encode `image` to approximately `pixel_width` ×
+/// `pixel_height` pixels, clamped so the result is never larger than the
+/// source. JPEG and WebP sources are re-encoded as JPEG, so that lossy
+/// photos aren't bloated back out as PNG; a lossless WebP source thus also
+/// comes out as JPEG. PNG, GIF, BMP and TIFF sources are re-encoded as PNG.
+fn try_downscale(image: &Image, pixel_width: u32, pixel_height: u32) -> Option<Image> {
+    let source = match image.format() {
+        ImageFormat::Raster(source) => source,
+        ImageFormat::Vector(_) => return None,
+    };
+
+    // Never upscale: clamp the requested target to the source's own size.
+    let pixel_width = pixel_width.min(image.width());
+    let pixel_height = pixel_height.min(image.height());
+
+    let decoded = image::load_from_memory(image.data()).ok()?;
+    let resized = decoded.resize(pixel_width, pixel_height, FilterType::Lanczos3);
+
+    let (output_format, result_format) = match source {
+        RasterFormat::Jpg | RasterFormat::Webp => {
+            (image::ImageOutputFormat::Jpeg(80), RasterFormat::Jpg)
+        }
+        RasterFormat::Png | RasterFormat::Gif | RasterFormat::Bmp | RasterFormat::Tiff => {
+            (image::ImageOutputFormat::Png, RasterFormat::Png)
+        }
+    };
+
+    let mut buf = Cursor::new(Vec::new());
+    resized.write_to(&mut buf, output_format).ok()?;
+
+    Image::new(Bytes::new(buf.into_inner()), ImageFormat::Raster(result_format)).ok()
+}
+
 impl Layout for ImageNode {
     fn layout(
         &self,
@@ -101,26 +378,25 @@ impl Layout for ImageNode {
 
         // Compute the actual size of the fitted image.
         let fit = styles.get(Self::FIT);
-        let fitted = match fit {
-            ImageFit::Cover | ImageFit::Contain => {
-                if wide == (fit == ImageFit::Contain) {
-                    Size::new(target.x, target.x / px_ratio)
-                } else {
-                    Size::new(target.y * px_ratio, target.y)
-                }
-            }
-            ImageFit::Stretch => target,
-        };
+        let fitted = fit_size(fit, target, wide, px_ratio, pxw, pxh);
+
+        // Downscale the image if it carries more pixels along either axis than
+        // its fitted size shows at the configured resolution.
+        let dpi = styles.get(Self::DPI);
+        let pixel_width = (fitted.x.to_pt() / 72.0 * dpi).round().max(1.0) as u32;
+        let pixel_height = (fitted.y.to_pt() / 72.0 * dpi).round().max(1.0) as u32;
+        let image = downscaled(&self.image, pixel_width, pixel_height);
 
         // First, place the image in a frame of exactly its size and then resize
-        // the frame to the target size, center aligning the image in the
-        // process.
+        // the frame to the target size, aligning the image within it as
+        // configured in the process.
+        let alignment = resolve_alignment(styles.get(Self::ALIGNMENT));
         let mut frame = Frame::new(fitted);
-        frame.push(Point::zero(), Element::Image(self.image.clone(), fitted));
-        frame.resize(target, Align::CENTER_HORIZON);
+        frame.push(Point::zero(), Element::Image(image, fitted));
+        frame.resize(target, alignment);
 
         // Create a clipping group if only part of the image should be visible.
-        if fit == ImageFit::Cover && !target.fits(fitted) {
+        if matches!(fit, ImageFit::Cover | ImageFit::None) && !target.fits(fitted) {
             frame.clip();
         }
 
@@ -140,6 +416,12 @@ pub enum ImageFit {
     Contain,
     /// The image should be stretched so that it exactly fills the area.
     Stretch,
+    /// The image should be displayed at its natural size, clipping if it
+    /// overflows the area.
+    None,
+    /// The image should be contained in the area without ever being enlarged
+    /// beyond its natural size.
+    ScaleDown,
 }
 
 castable! {
@@ -151,4 +433,197 @@ castable! {
     /// The image should be stretched so that it exactly fills the area, even if
     /// this means that the image will be distorted.
     "stretch" => Self::Stretch,
+    /// The image should be displayed at its natural size, clipping if it
+    /// overflows the area.
+    "none" => Self::None,
+    /// The image should be contained in the area without ever being enlarged
+    /// beyond its natural size.
+    "scale-down" => Self::ScaleDown,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_format_accepts_recognized_names_case_insensitively() {
+        assert_eq!(parse_format("PNG"), Ok(ImageFormat::Raster(RasterFormat::Png)));
+        assert_eq!(parse_format("jpeg"), Ok(ImageFormat::Raster(RasterFormat::Jpg)));
+        assert_eq!(parse_format("svg"), Ok(ImageFormat::Vector(VectorFormat::Svg)));
+    }
+
+    #[test]
+    fn parse_format_rejects_unrecognized_names_instead_of_sniffing() {
+        // An explicit, wrong `format` argument must error, not silently fall
+        // back to guessing the format from the bytes.
+        assert!(parse_format("pdf").is_err());
+    }
+
+    #[test]
+    fn determine_format_uses_extension_when_recognized() {
+        assert_eq!(
+            determine_format("PNG", b"not actually a png"),
+            Ok(ImageFormat::Raster(RasterFormat::Png)),
+        );
+        assert_eq!(
+            determine_format("webp", b""),
+            Ok(ImageFormat::Raster(RasterFormat::Webp)),
+        );
+    }
+
+    #[test]
+    fn determine_format_falls_back_to_sniffing() {
+        assert_eq!(
+            determine_format("", b"\x89PNG\r\n\x1a\n..."),
+            Ok(ImageFormat::Raster(RasterFormat::Png)),
+        );
+        assert_eq!(
+            determine_format("dat", b"GIF89a..."),
+            Ok(ImageFormat::Raster(RasterFormat::Gif)),
+        );
+    }
+
+    #[test]
+    fn sniff_format_detects_all_supported_magic_bytes() {
+        assert_eq!(
+            sniff_format(b"\xff\xd8\xff\xe0"),
+            Ok(ImageFormat::Raster(RasterFormat::Jpg)),
+        );
+        assert_eq!(
+            sniff_format(b"RIFF\0\0\0\0WEBPVP8 "),
+            Ok(ImageFormat::Raster(RasterFormat::Webp)),
+        );
+        assert_eq!(sniff_format(b"BM..."), Ok(ImageFormat::Raster(RasterFormat::Bmp)));
+        assert_eq!(
+            sniff_format(b"II*\0..."),
+            Ok(ImageFormat::Raster(RasterFormat::Tiff)),
+        );
+        assert_eq!(
+            sniff_format(b"MM\0*..."),
+            Ok(ImageFormat::Raster(RasterFormat::Tiff)),
+        );
+        assert_eq!(
+            sniff_format(b"<svg xmlns=..."),
+            Ok(ImageFormat::Vector(VectorFormat::Svg)),
+        );
+    }
+
+    #[test]
+    fn sniff_format_rejects_unknown_content() {
+        assert!(sniff_format(b"not an image").is_err());
+    }
+
+    #[test]
+    fn needs_downscale_is_false_when_target_already_fits_both_axes() {
+        assert!(!needs_downscale(800, 600, 400, 300));
+    }
+
+    #[test]
+    fn needs_downscale_is_true_when_only_one_axis_is_oversized() {
+        // A target that's narrower than the image but already tall enough
+        // must still trigger a downscale, not be skipped.
+        assert!(needs_downscale(400, 600, 800, 300));
+        assert!(needs_downscale(800, 200, 400, 600));
+    }
+
+    #[test]
+    fn needs_downscale_is_true_when_both_axes_are_oversized() {
+        assert!(needs_downscale(400, 300, 800, 600));
+    }
+
+    #[test]
+    fn hash_bytes_is_deterministic_and_content_sensitive() {
+        assert_eq!(hash_bytes(b"same"), hash_bytes(b"same"));
+        assert_ne!(hash_bytes(b"same"), hash_bytes(b"diff"));
+    }
+
+    #[test]
+    fn downscale_cache_evicts_oldest_entry_past_capacity() {
+        let mut cache = DownscaleCache::new();
+        for i in 0..DOWNSCALE_CACHE_CAP {
+            cache.insert((i as u64, 1, 1), dummy_image());
+        }
+        // Check presence without `get`, which would mark key 0 as recently
+        // used and protect it from eviction.
+        assert!(cache.map.contains_key(&(0, 1, 1)));
+
+        cache.insert((DOWNSCALE_CACHE_CAP as u64, 1, 1), dummy_image());
+
+        // The oldest entry (key 0) was evicted to make room.
+        assert!(cache.get(&(0, 1, 1)).is_none());
+        assert!(cache.get(&(DOWNSCALE_CACHE_CAP as u64, 1, 1)).is_some());
+    }
+
+    #[test]
+    fn downscale_cache_touch_protects_recently_used_entry_from_eviction() {
+        let mut cache = DownscaleCache::new();
+        for i in 0..DOWNSCALE_CACHE_CAP {
+            cache.insert((i as u64, 1, 1), dummy_image());
+        }
+
+        // Touch key 0 so it's no longer the least recently used entry.
+        cache.get(&(0, 1, 1));
+        cache.insert((DOWNSCALE_CACHE_CAP as u64, 1, 1), dummy_image());
+
+        assert!(cache.get(&(0, 1, 1)).is_some());
+        // Key 1 is now the oldest untouched entry and should be evicted instead.
+        assert!(cache.get(&(1, 1, 1)).is_none());
+    }
+
+    /// A minimal valid 1×1 transparent PNG, used where the cache needs a
+    /// real, decodable `Image` but its pixel content is irrelevant.
+    const DUMMY_PNG: &[u8] = &[
+        0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, 0x00, 0x00, 0x00, 0x0d, 0x49, 0x48,
+        0x44, 0x52, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x08, 0x06, 0x00, 0x00,
+        0x00, 0x1f, 0x15, 0xc4, 0x89, 0x00, 0x00, 0x00, 0x0a, 0x49, 0x44, 0x41, 0x54, 0x78,
+        0x9c, 0x63, 0x00, 0x01, 0x00, 0x00, 0x05, 0x00, 0x01, 0x0d, 0x0a, 0x2d, 0xb4, 0x00,
+        0x00, 0x00, 0x00, 0x49, 0x45, 0x4e, 0x44, 0xae, 0x42, 0x60, 0x82,
+    ];
+
+    fn dummy_image() -> Image {
+        Image::new(Bytes::new(DUMMY_PNG.to_vec()), ImageFormat::Raster(RasterFormat::Png))
+            .expect("dummy image")
+    }
+
+    #[test]
+    fn resolve_alignment_defaults_to_center_horizon() {
+        assert_eq!(resolve_alignment(Smart::Auto), Align::CENTER_HORIZON);
+    }
+
+    #[test]
+    fn resolve_alignment_uses_explicit_value_when_given() {
+        assert_eq!(resolve_alignment(Smart::Custom(Align::LEFT_TOP)), Align::LEFT_TOP);
+    }
+
+    #[test]
+    fn fit_size_stretch_returns_target_untouched() {
+        let target = Size::new(Abs::pt(200.0), Abs::pt(100.0));
+        assert_eq!(fit_size(ImageFit::Stretch, target, true, 2.0, 400.0, 200.0), target);
+    }
+
+    #[test]
+    fn fit_size_none_uses_natural_pixel_size_regardless_of_target() {
+        let target = Size::new(Abs::pt(10.0), Abs::pt(10.0));
+        let fitted = fit_size(ImageFit::None, target, true, 2.5, 200.0, 80.0);
+        assert_eq!(fitted, Size::new(Abs::pt(200.0), Abs::pt(80.0)));
+    }
+
+    #[test]
+    fn fit_size_scale_down_clamps_to_natural_size_when_target_is_larger() {
+        // A small 100x50 source placed into a much larger target must not be
+        // enlarged.
+        let target = Size::new(Abs::pt(1000.0), Abs::pt(1000.0));
+        let fitted = fit_size(ImageFit::ScaleDown, target, false, 2.0, 100.0, 50.0);
+        assert_eq!(fitted, Size::new(Abs::pt(100.0), Abs::pt(50.0)));
+    }
+
+    #[test]
+    fn fit_size_scale_down_behaves_like_contain_when_target_is_smaller() {
+        // A large source shrinking into a smaller target behaves exactly
+        // like `Contain`.
+        let target = Size::new(Abs::pt(20.0), Abs::pt(20.0));
+        let contain = fit_size(ImageFit::Contain, target, true, 2.0, 400.0, 200.0);
+        let scale_down = fit_size(ImageFit::ScaleDown, target, true, 2.0, 400.0, 200.0);
+        assert_eq!(scale_down, contain);
+    }
 }
@@ -0,0 +1,95 @@
+//! Raster and vector image handling.
+
+use std::io::Cursor;
+use std::sync::Arc;
+
+/// A shared, clonable byte buffer.
+pub type Bytes = Arc<Vec<u8>>;
+
+/// A result type with a string error message.
+pub type StrResult<T> = Result<T, String>;
+
+/// A decoded image.
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+pub struct Image {
+    buffer: Bytes,
+    format: ImageFormat,
+    width: u32,
+    height: u32,
+}
+
+impl Image {
+    /// Decode an image from bytes in one of the supported formats.
+    pub fn new(buffer: Bytes, format: ImageFormat) -> StrResult<Self> {
+        let (width, height) = match format {
+            ImageFormat::Raster(_) => {
+                image::io::Reader::new(Cursor::new(buffer.as_slice()))
+                    .with_guessed_format()
+                    .map_err(|err| err.to_string())?
+                    .into_dimensions()
+                    .map_err(|err| err.to_string())?
+            }
+            ImageFormat::Vector(VectorFormat::Svg) => {
+                let tree = usvg::Tree::from_data(&buffer, &usvg::Options::default().to_ref())
+                    .map_err(|err| err.to_string())?;
+                let size = tree.svg_node().size;
+                (size.width().ceil() as u32, size.height().ceil() as u32)
+            }
+        };
+
+        Ok(Self { buffer, format, width, height })
+    }
+
+    /// The raw image data.
+    pub fn data(&self) -> &Bytes {
+        &self.buffer
+    }
+
+    /// The format the image was encoded in.
+    pub fn format(&self) -> ImageFormat {
+        self.format
+    }
+
+    /// The width of the image in pixels.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// The height of the image in pixels.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+}
+
+/// The format of an image file.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum ImageFormat {
+    /// A raster graphics format, like PNG or JPEG.
+    Raster(RasterFormat),
+    /// A vector graphics format, like SVG.
+    Vector(VectorFormat),
+}
+
+/// A raster graphics format.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum RasterFormat {
+    /// Raster format for illustrations and transparent graphics.
+    Png,
+    /// Lossy raster format well suited for photos.
+    Jpg,
+    /// Raster format that is typically only used for small animations.
+    Gif,
+    /// Raster format that supports both lossy and lossless compression.
+    Webp,
+    /// Raster format for uncompressed, lossless bitmaps.
+    Bmp,
+    /// Raster format for scanned documents and high-quality photos.
+    Tiff,
+}
+
+/// A vector graphics format.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum VectorFormat {
+    /// The vector graphics format of the web.
+    Svg,
+}
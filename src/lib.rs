@@ -0,0 +1,3 @@
+//! The compiler for the _Typst_ markup language.
+
+pub mod image;